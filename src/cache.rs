@@ -0,0 +1,112 @@
+use crate::scanner::Finding;
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+/// Where the cache went before it moved out of the project; still used when
+/// there is no home directory to put it under.
+pub const LEGACY_CACHE_FILE: &str = ".sieve_cache.json";
+
+// Enough of the project path's hash to keep projects apart
+const PROJECT_KEY_LEN: usize = 16;
+
+/// The findings of the last `check`, for `check --fix <index>`, kept under
+/// the user's cache directory so it is never scanned or committed.
+pub fn path() -> PathBuf {
+    let project = std::env::current_dir()
+        .and_then(|dir| dir.canonicalize())
+        .unwrap_or_else(|_| PathBuf::from("."));
+    let cache_home = std::env::var_os("XDG_CACHE_HOME")
+        .or_else(|| {
+            cfg!(windows)
+                .then(|| std::env::var_os("LOCALAPPDATA"))
+                .flatten()
+        })
+        .map(PathBuf::from);
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+    resolve_path(&project, cache_home, home)
+}
+
+/// `<cache home>/sieve/<hash of the project path>.json`, where the cache home
+/// is $XDG_CACHE_HOME (or %LOCALAPPDATA%), else `~/.cache`. Relative cache
+/// homes are ignored, as the XDG spec asks.
+pub fn resolve_path(project: &Path, cache_home: Option<PathBuf>, home: Option<PathBuf>) -> PathBuf {
+    let Some(root) = cache_home.filter(|dir| dir.is_absolute()).or_else(|| {
+        home.filter(|dir| dir.is_absolute())
+            .map(|dir| dir.join(".cache"))
+    }) else {
+        return PathBuf::from(LEGACY_CACHE_FILE);
+    };
+    let key = hex::encode(Sha256::digest(project.to_string_lossy().as_bytes()));
+    root.join("sieve")
+        .join(format!("{}.json", &key[..PROJECT_KEY_LEN]))
+}
+
+pub fn save(path: &Path, findings: &[Finding]) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create cache directory {}", parent.display()))?;
+    }
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create cache file {}", path.display()))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), findings)?;
+    Ok(())
+}
+
+/// The cached findings, or None if no `check` has written any for this
+/// project.
+pub fn load(path: &Path) -> Result<Option<Vec<Finding>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open cache file {}", path.display()))?;
+    let findings = serde_json::from_reader(file)
+        .with_context(|| format!("Invalid cache file {}", path.display()))?;
+    Ok(Some(findings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_path_keyed_by_project() {
+        let project = Path::new("/home/dev/src/app");
+        let path = resolve_path(
+            project,
+            Some(PathBuf::from("/var/cache/dev")),
+            Some(PathBuf::from("/home/dev")),
+        );
+        assert!(path.starts_with("/var/cache/dev/sieve"));
+        assert_eq!(path.extension().unwrap(), "json");
+
+        // Without $XDG_CACHE_HOME, or with a relative one, ~/.cache is used
+        let from_home = resolve_path(project, None, Some(PathBuf::from("/home/dev")));
+        assert!(from_home.starts_with("/home/dev/.cache/sieve"));
+        assert_eq!(
+            resolve_path(
+                project,
+                Some(PathBuf::from("cache")),
+                Some(PathBuf::from("/home/dev"))
+            ),
+            from_home
+        );
+        assert_eq!(from_home.file_name(), path.file_name());
+
+        let other = resolve_path(
+            Path::new("/home/dev/src/other"),
+            None,
+            Some(PathBuf::from("/home/dev")),
+        );
+        assert_ne!(other, from_home);
+        assert_eq!(
+            resolve_path(project, None, None),
+            PathBuf::from(LEGACY_CACHE_FILE)
+        );
+    }
+}
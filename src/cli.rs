@@ -1,4 +1,10 @@
-use clap::{Parser, Subcommand};
+use crate::fixer::RepairMode;
+use crate::scanner::Severity;
+use clap::{Parser, Subcommand, ValueEnum};
+use log::LevelFilter;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "sieve")]
@@ -12,7 +18,8 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub no_tui: bool,
 
-    /// Output format when TUI is disabled (human or json)
+    /// Output format when TUI is disabled (human, json, json-summary, jsonl,
+    /// grep, github, junit, or sarif)
     #[arg(long, global = true, default_value = "human")]
     pub format: String,
 
@@ -20,26 +27,257 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub strict: bool,
 
+    /// Write the report to this file instead of stdout (in non-TUI mode);
+    /// parent directories are created as needed
+    #[arg(long, global = true, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Print nothing but findings: no all-clear message, progress, or
+    /// warnings (errors still go to stderr); the exit code is unchanged
+    #[arg(long, global = true)]
+    pub quiet: bool,
+
     /// Show detailed info for all findings (in non-TUI mode)
     #[arg(long, global = true)]
     pub verbose: bool,
+
+    /// Print why the process exits nonzero (in non-TUI mode)
+    #[arg(long, global = true)]
+    pub explain_exit: bool,
+
+    /// Include per-finding scoring signals in JSON output
+    #[arg(long, global = true)]
+    pub emit_signals: bool,
+
+    /// Annotate findings with the commit/author that last touched the line (slow)
+    #[arg(long, global = true)]
+    pub blame: bool,
+
+    /// Cap redacted previews (JSON, TUI, clipboard) at this many characters
+    #[arg(long, global = true)]
+    pub max_preview_length: Option<usize>,
+
+    /// Replace file paths in reports (in non-TUI mode) and syslog messages
+    /// with salted hashes, keeping the extension; the salt is printed on stderr
+    #[arg(long, global = true)]
+    pub anonymize_paths: bool,
+
+    /// Also collect and report Low findings (score 40-59)
+    #[arg(long, global = true)]
+    pub include_low: bool,
+
+    /// Also look for secrets inside base64-encoded values (slower)
+    #[arg(long, global = true)]
+    pub decode_base64: bool,
+
+    /// Report findings of one rule on consecutive lines as a single finding
+    /// spanning them (always on for private key blocks)
+    #[arg(long, global = true)]
+    pub merge_adjacent: bool,
+
+    /// Report a secret repeated within a file once, listing every line it
+    /// is on (repairs still rewrite each of them)
+    #[arg(long, global = true)]
+    pub dedupe: bool,
+
+    /// Give up on a single file after this long, e.g. 500ms or 2s
+    /// (skipped files are listed with --verbose)
+    #[arg(long, global = true, value_parser = parse_duration)]
+    pub timeout_per_file: Option<Duration>,
+
+    /// Skip files larger than this, e.g. 512K or 20M (default 5M; 0 for no
+    /// limit; skipped files are listed with --verbose)
+    #[arg(long, global = true, value_parser = parse_size)]
+    pub max_file_size: Option<u64>,
+
+    /// Scan files on this many threads (default: one per logical CPU; 1
+    /// scans sequentially)
+    #[arg(long, global = true, value_name = "N")]
+    pub concurrency: Option<NonZeroUsize>,
+
+    /// Lowest severity that fails the run: high, medium, low, or none
+    /// (default high; medium with --strict)
+    #[arg(long, global = true, value_enum)]
+    pub gate: Option<Gate>,
+
+    /// Fail the run whenever this rule matches, even under --gate none;
+    /// repeatable (adds to always_fail_rules in sieve.toml)
+    #[arg(long = "always-fail-rule", global = true, value_name = "RULE_ID")]
+    pub always_fail_rules: Vec<String>,
+
+    /// Only run this rule; repeatable, and any other rule is off (adds to
+    /// enable_rules in sieve.toml)
+    #[arg(long = "enable-rule", global = true, value_name = "RULE_ID")]
+    pub enable_rules: Vec<String>,
+
+    /// Never run this rule; repeatable (adds to disable_rules in sieve.toml)
+    #[arg(long = "disable-rule", global = true, value_name = "RULE_ID")]
+    pub disable_rules: Vec<String>,
+
+    /// Drop findings scoring below this (default 60; overrides sieve.toml)
+    #[arg(long, global = true)]
+    pub min_score: Option<u8>,
+
+    /// Diagnostics shown on stderr: error, warn, info, debug, or trace
+    /// (overrides $SIEVE_LOG; default warn, or info with --verbose)
+    #[arg(long, global = true, value_enum)]
+    pub log_level: Option<LogLevel>,
+
+    /// Also send each finding to the local syslog as an RFC 5424 message
+    #[arg(long, global = true)]
+    pub syslog: bool,
+
+    /// Send syslog messages over UDP to this collector (host:port) instead;
+    /// implies --syslog
+    #[arg(long, global = true, value_name = "HOST:PORT")]
+    pub syslog_host: Option<String>,
+
+    /// Syslog facility, e.g. auth or local4 (overrides sieve.toml)
+    #[arg(long, global = true)]
+    pub syslog_facility: Option<String>,
+
+    /// Post a summary of the findings (counts and redacted previews) to this
+    /// Slack incoming webhook (in non-TUI mode)
+    #[arg(long, global = true, value_name = "WEBHOOK_URL")]
+    pub notify_slack: Option<String>,
+
+    /// Append each repair, baseline entry and restore to sieve-actions.jsonl
+    /// (timestamp, user, file, fingerprint, placeholder or reason)
+    #[arg(long, global = true)]
+    pub audit_log: bool,
+
+    /// File of secret values to never report: literals, `regex:<pattern>`,
+    /// or `sha256:<hex>` (default ./allowlist.txt, if present)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub allowlist: Option<PathBuf>,
+
+    /// TOML file of custom `[[rule]]`s (id, pattern, severity, score)
+    /// checked after the built-in rules (overrides $SIEVE_RULES and
+    /// ./sieve-rules.toml, which is used if present)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub rules: Option<PathBuf>,
+
+    /// Path to the config file (overrides $SIEVE_CONFIG and ./sieve.toml)
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+}
+
+impl Cli {
+    pub fn gate(&self) -> Gate {
+        self.gate.unwrap_or(if self.strict {
+            Gate::Medium
+        } else {
+            Gate::High
+        })
+    }
+}
+
+/// Parses `250ms`, `2s`, or a bare number of seconds.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration '{}' (expected e.g. 500ms or 2s)", value);
+    if let Some(ms) = value.strip_suffix("ms") {
+        ms.parse().map(Duration::from_millis).map_err(|_| invalid())
+    } else {
+        let secs = value.strip_suffix('s').unwrap_or(value);
+        secs.parse::<f64>()
+            .ok()
+            .and_then(|s| Duration::try_from_secs_f64(s).ok())
+            .ok_or_else(invalid)
+    }
+}
+
+/// Parses a byte count with an optional K, M or G suffix (powers of 1024).
+fn parse_size(value: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid size '{}' (expected e.g. 512K or 20M)", value);
+    let upper = value.trim().to_ascii_uppercase();
+    let digits = upper.strip_suffix('B').unwrap_or(&upper);
+    let (number, unit) = match digits.char_indices().last() {
+        Some((i, 'K')) => (&digits[..i], 1 << 10),
+        Some((i, 'M')) => (&digits[..i], 1 << 20),
+        Some((i, 'G')) => (&digits[..i], 1 << 30),
+        _ => (digits, 1),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit))
+        .ok_or_else(invalid)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Gate {
+    High,
+    Medium,
+    Low,
+    None,
+}
+
+impl Gate {
+    /// The lowest severity that fails the run, if any does.
+    pub fn threshold(self) -> Option<Severity> {
+        match self {
+            Gate::High => Some(Severity::High),
+            Gate::Medium => Some(Severity::Medium),
+            Gate::Low => Some(Severity::Low),
+            Gate::None => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn filter(self) -> LevelFilter {
+        match self {
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Scan for secrets
     Scan {
+        /// Scan exactly these files: no directory walk, ignore files, or git
+        files: Vec<PathBuf>,
+
         /// Scan staged files (git diff --cached)
         #[arg(long)]
         staged: bool,
 
-        /// Scan a specific path (recursive)
-        #[arg(long)]
-        path: Option<String>,
+        /// Scan specific paths (recursive); repeat or list several: --path src config
+        #[arg(long, num_args = 1..)]
+        path: Vec<String>,
 
-        /// Scan changes since a specific git reference
+        /// Scan changes since a specific git reference (branch names use the merge base),
+        /// or in an explicit range such as main..feature or origin/main...HEAD
         #[arg(long)]
         since: Option<String>,
+
+        /// Scan only the lines a single commit added (a merge commit is
+        /// compared with its first parent)
+        #[arg(long, value_name = "SHA")]
+        commit: Option<String>,
+
+        /// Skip files matching this glob (relative to the scan root); repeatable
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Also scan files excluded by .gitignore/.ignore
+        #[arg(long)]
+        no_ignore: bool,
     },
     /// Manage baseline (ignore known secrets)
     Baseline {
@@ -50,6 +288,76 @@ pub enum Commands {
         /// Check against baseline (only report new findings)
         #[arg(long)]
         check: bool,
+
+        /// Run a full scan and drop baseline entries whose secret is no
+        /// longer in the code
+        #[arg(long)]
+        prune: bool,
+
+        /// Add another baseline's entries to this one (existing entries win
+        /// where both have the same fingerprint)
+        #[arg(long, value_name = "FILE")]
+        merge: Option<PathBuf>,
+
+        #[command(subcommand)]
+        action: Option<BaselineAction>,
+    },
+    /// Check whether detected AWS, GitHub and Stripe credentials still work,
+    /// with one read-only API call each (needs network access)
+    Verify {
+        /// Paths to scan (recursive); defaults to the current directory
+        #[arg(long, num_args = 1..)]
+        path: Vec<String>,
+
+        /// Skip files matching this glob (relative to the scan root); repeatable
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Also scan files excluded by .gitignore/.ignore
+        #[arg(long)]
+        no_ignore: bool,
+    },
+    /// Final remediation gate: print CLEAN and exit 0 only if nothing would fail the run
+    VerifyClean {
+        /// Also scan every commit reachable from any ref
+        #[arg(long)]
+        history: bool,
+    },
+    /// Scan the current branch's past commits for secrets, reporting each
+    /// with the commit that added it
+    History {
+        /// Only scan the newest N commits
+        #[arg(long, value_name = "N")]
+        max_count: Option<usize>,
+
+        /// Only scan commits made after this date, e.g. 2024-01-01 or "2 weeks ago"
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+    },
+    /// Print precision/recall at each --min-score over a labeled set of lines
+    Calibrate {
+        /// JSON Lines file of labeled examples:
+        /// {"line": "...", "secret": true, "path": "optional/file.py"}
+        #[arg(long)]
+        labels: PathBuf,
+    },
+    /// Undo repairs made with --backup: move every <file>.sieve.bak under
+    /// the current directory back over its file
+    Restore,
+    /// Install/uninstall the pre-commit hook, or run as a server-side hook
+    Hook {
+        /// `install` writes .git/hooks/pre-commit, `uninstall` removes it
+        #[arg(value_enum)]
+        action: Option<HookAction>,
+
+        /// Add Sieve to a pre-commit hook that already exists
+        #[arg(long)]
+        force: bool,
+
+        /// Server-side pre-receive mode: read `<old> <new> <ref>` lines from
+        /// stdin, scan the pushed commits, and reject the push on findings
+        #[arg(long)]
+        pre_receive: bool,
     },
     /// Check for secrets with advanced options (repair, fix)
     Check {
@@ -61,8 +369,54 @@ pub enum Commands {
         #[arg(long)]
         repair: bool,
 
-        /// Fix a specific finding by index
+        /// How --repair and --fix rewrite secrets: `placeholder` writes
+        /// REDACTED_SECRET, `env` moves the value to .env and reads it from the
+        /// environment (JS/TS and Python)
+        #[arg(long, value_enum, default_value_t)]
+        repair_mode: RepairMode,
+
+        /// With --repair or --fix, append a comment with the finding's
+        /// fingerprint (never the secret) to each repaired line
+        #[arg(long)]
+        annotate: bool,
+
+        /// Keep each repaired file's original as <file>.sieve.bak, to undo
+        /// with `sieve restore`
+        #[arg(long)]
+        backup: bool,
+
+        /// With --repair or --fix, print each change as a diff and write
+        /// nothing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Fix a specific finding by index (from the last check's cache)
         #[arg(long)]
         fix: Option<usize>,
+
+        /// Don't remember the findings for a later --fix
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Skip files matching this glob (relative to the scan root); repeatable
+        #[arg(long)]
+        exclude: Vec<String>,
+
+        /// Also scan files excluded by .gitignore/.ignore
+        #[arg(long)]
+        no_ignore: bool,
     },
 }
+
+#[derive(Subcommand)]
+pub enum BaselineAction {
+    /// Write the suppressed findings (file, rule, preview, created_at,
+    /// reason) for review; --format csv (default) or json, to --output or stdout
+    Export,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum HookAction {
+    Install,
+    Uninstall,
+}
@@ -0,0 +1,172 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use copypasta::{ClipboardContext, ClipboardProvider};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+// Another program holding the clipboard (common on Windows) fails a copy
+// briefly; a missing clipboard (no display, e.g. over SSH) isn't retried
+const ATTEMPTS: usize = 3;
+const RETRY_DELAY: Duration = Duration::from_millis(50);
+
+// Terminals drop OSC 52 payloads much larger than this
+const OSC52_MAX_LEN: usize = 100_000;
+// Matched against the start of $TERM, which SSH forwards
+const OSC52_TERMS: &[&str] = &[
+    "xterm-kitty",
+    "xterm-ghostty",
+    "alacritty",
+    "foot",
+    "wezterm",
+    "contour",
+];
+const OSC52_PROGRAMS: &[&str] = &["iTerm.app", "WezTerm", "ghostty"];
+
+/// Copies `content` and returns the status line for the TUI. Without a
+/// system clipboard, the text goes to the terminal's clipboard (OSC 52) if
+/// the terminal is known to accept it, else to a file in the temp directory.
+pub fn copy(content: &str) -> String {
+    let Err(reason) = copy_to_system(content) else {
+        return "Copied to clipboard!".to_string();
+    };
+    if osc52_supported(|name| std::env::var(name).ok()) {
+        if let Some(sequence) = osc52_sequence(content) {
+            let mut stdout = io::stdout();
+            if stdout
+                .write_all(sequence.as_bytes())
+                .and_then(|()| stdout.flush())
+                .is_ok()
+            {
+                return "Sent to the terminal's clipboard (OSC 52)".to_string();
+            }
+        }
+    }
+    let path = fallback_path(&std::env::temp_dir(), std::process::id());
+    match save(&path, content) {
+        Ok(()) => fallback_message(&reason, &path),
+        Err(e) => format!("{}; couldn't save to {}: {}", reason, path.display(), e),
+    }
+}
+
+fn copy_to_system(content: &str) -> Result<(), String> {
+    let Ok(mut ctx) = ClipboardContext::new() else {
+        return Err("Clipboard unavailable".to_string());
+    };
+    let mut result = ctx.set_contents(content.to_string());
+    for _ in 1..ATTEMPTS {
+        if result.is_ok() {
+            break;
+        }
+        thread::sleep(RETRY_DELAY);
+        result = ctx.set_contents(content.to_string());
+    }
+    result.map_err(|e| format!("Copy failed: {}", e))
+}
+
+/// Whether the terminal accepts clipboard writes over OSC 52, judged by
+/// $TERM and $TERM_PROGRAM. `SIEVE_OSC52=1` (or `0`) overrides the guess,
+/// e.g. inside tmux, whose $TERM says nothing about the outer terminal.
+fn osc52_supported(env: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(forced) = env("SIEVE_OSC52") {
+        return forced != "0";
+    }
+    let term = env("TERM").unwrap_or_default();
+    let program = env("TERM_PROGRAM").unwrap_or_default();
+    OSC52_TERMS.iter().any(|t| term.starts_with(t)) || OSC52_PROGRAMS.contains(&program.as_str())
+}
+
+/// The escape sequence that sets the clipboard, or None if it's too long
+/// for terminals to take.
+fn osc52_sequence(content: &str) -> Option<String> {
+    let encoded = STANDARD.encode(content);
+    (encoded.len() <= OSC52_MAX_LEN).then(|| format!("\x1b]52;c;{}\x07", encoded))
+}
+
+/// One file per session, overwritten by each copy.
+fn fallback_path(temp_dir: &Path, pid: u32) -> PathBuf {
+    temp_dir.join(format!("sieve-clipboard-{}.txt", pid))
+}
+
+fn fallback_message(reason: &str, path: &Path) -> String {
+    format!("{}; saved to {}", reason, path.display())
+}
+
+/// Writes `content` readable only by the user. An earlier copy is removed
+/// and the file created anew, so a link planted at the path isn't followed.
+fn save(path: &Path, content: &str) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(content.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_fallback_file_path_and_message() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = fallback_path(dir.path(), 4242);
+        assert_eq!(path, dir.path().join("sieve-clipboard-4242.txt"));
+        assert_eq!(
+            fallback_message("Clipboard unavailable", &path),
+            format!("Clipboard unavailable; saved to {}", path.display())
+        );
+
+        save(&path, "Sieve Alert!\nRule: AWS_ACCESS_KEY").unwrap();
+        // A second copy replaces the first
+        save(&path, "Sieve Alert!\nRule: STRIPE_KEY").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "Sieve Alert!\nRule: STRIPE_KEY"
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_osc52_detection_and_sequence() {
+        let env = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            move |name: &str| vars.get(name).cloned()
+        };
+        assert!(osc52_supported(env(&[("TERM", "xterm-kitty")])));
+        assert!(osc52_supported(env(&[
+            ("TERM", "xterm-256color"),
+            ("TERM_PROGRAM", "WezTerm")
+        ])));
+        assert!(!osc52_supported(env(&[("TERM", "xterm-256color")])));
+        assert!(!osc52_supported(env(&[])));
+        assert!(osc52_supported(env(&[
+            ("TERM", "tmux-256color"),
+            ("SIEVE_OSC52", "1")
+        ])));
+        assert!(!osc52_supported(env(&[
+            ("TERM", "alacritty"),
+            ("SIEVE_OSC52", "0")
+        ])));
+
+        assert_eq!(osc52_sequence("hi").unwrap(), "\x1b]52;c;aGk=\x07");
+        assert!(osc52_sequence(&"x".repeat(OSC52_MAX_LEN)).is_none());
+    }
+}
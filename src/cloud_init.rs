@@ -0,0 +1,277 @@
+//! cloud-init user-data: the files a VM writes at boot (`write_files`) and the
+//! commands it runs (`runcmd`). Parsed line by line, like the Helm and
+//! Kubernetes handling in the scanner, so findings keep their line numbers.
+
+// File names provisioning tools conventionally give user-data
+const USER_DATA_NAMES: &[&str] = &[
+    "user-data",
+    "user-data.yaml",
+    "user-data.yml",
+    "cloud-init.yaml",
+    "cloud-init.yml",
+    "cloud-config.yaml",
+    "cloud-config.yml",
+];
+const HEADER: &str = "#cloud-config";
+
+/// A run of text inside one line of the user-data file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub line: usize,
+    /// Byte offset of `text` within the line
+    pub start: usize,
+    pub text: String,
+}
+
+/// One `write_files` entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WriteFile {
+    /// Where the file lands on the VM
+    pub path: Option<String>,
+    pub encoding: Option<String>,
+    pub content: Vec<Span>,
+}
+
+impl WriteFile {
+    /// True for `b64`/`base64`; gzipped content (`gz+b64`) isn't decoded.
+    pub fn is_base64(&self) -> bool {
+        matches!(self.encoding.as_deref(), Some("b64" | "base64"))
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct CloudInit {
+    pub write_files: Vec<WriteFile>,
+    /// One span per command (or per line of a block command)
+    pub runcmd: Vec<Span>,
+}
+
+pub fn is_cloud_init(path: &str, content: &str) -> bool {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    USER_DATA_NAMES.contains(&name.to_lowercase().as_str())
+        || content
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .is_some_and(|l| l.trim_end() == HEADER)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    WriteFiles,
+    RunCmd,
+    Other,
+}
+
+pub fn parse(content: &str) -> CloudInit {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut parsed = CloudInit::default();
+    let mut section = Section::Other;
+    // Indent of the fields of the current write_files item
+    let mut field_indent = None;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let indent = indent_of(line);
+        let trimmed = line.trim();
+        i += 1;
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if indent == 0 && !trimmed.starts_with('-') {
+            section = match trimmed.split(':').next() {
+                Some("write_files") => Section::WriteFiles,
+                Some("runcmd") => Section::RunCmd,
+                _ => Section::Other,
+            };
+            field_indent = None;
+            continue;
+        }
+
+        let item = trimmed
+            .strip_prefix('-')
+            .filter(|rest| rest.is_empty() || rest.starts_with(' ') || rest.starts_with('\t'));
+        match section {
+            Section::WriteFiles => {
+                let (start, field) = match item {
+                    Some(rest) => {
+                        parsed.write_files.push(WriteFile::default());
+                        let start = indent + 1 + indent_of(rest);
+                        field_indent = Some(start);
+                        (start, rest.trim_start())
+                    }
+                    None if Some(indent) == field_indent => (indent, trimmed),
+                    None => continue,
+                };
+                let Some(file) = parsed.write_files.last_mut() else {
+                    continue;
+                };
+                let Some((key, value, value_start)) = split_field(field, start) else {
+                    continue;
+                };
+                match key {
+                    "path" => file.path = Some(unquote(value).0.to_string()),
+                    "encoding" => file.encoding = Some(unquote(value).0.to_lowercase()),
+                    "content" if is_block_indicator(value) => {
+                        let (spans, next) = block_scalar(&lines, i, start);
+                        file.content = spans;
+                        i = next;
+                    }
+                    "content" if !value.is_empty() => {
+                        let (text, offset) = unquote(value);
+                        file.content = vec![Span {
+                            line: i,
+                            start: value_start + offset,
+                            text: text.to_string(),
+                        }];
+                    }
+                    _ => {}
+                }
+            }
+            Section::RunCmd => {
+                let Some(rest) = item else {
+                    continue;
+                };
+                let value = rest.trim_start();
+                let value_start = indent + 1 + indent_of(rest);
+                if is_block_indicator(value) {
+                    let (spans, next) = block_scalar(&lines, i, indent);
+                    parsed.runcmd.extend(spans);
+                    i = next;
+                } else if !value.is_empty() {
+                    let (text, offset) = unquote(value);
+                    parsed.runcmd.push(Span {
+                        line: i,
+                        start: value_start + offset,
+                        text: text.to_string(),
+                    });
+                }
+            }
+            Section::Other => {}
+        }
+    }
+    parsed
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// `key: value` at byte `start` of its line: (key, value, value's offset).
+fn split_field(field: &str, start: usize) -> Option<(&str, &str, usize)> {
+    let (key, rest) = field.split_once(':')?;
+    let value = rest.trim();
+    let value_start = start + key.len() + 1 + indent_of(rest);
+    Some((key.trim(), value, value_start))
+}
+
+/// `|`, `|-`, `>` and friends start a block scalar on the following lines.
+fn is_block_indicator(value: &str) -> bool {
+    value.starts_with('|') || value.starts_with('>')
+}
+
+/// The lines of a block scalar starting at index `from`, indented deeper than
+/// `parent_indent`, and the index of the first line after it.
+fn block_scalar(lines: &[&str], from: usize, parent_indent: usize) -> (Vec<Span>, usize) {
+    let mut spans = Vec::new();
+    let mut i = from;
+    while i < lines.len() {
+        let line = lines[i];
+        if !line.trim().is_empty() {
+            let indent = indent_of(line);
+            if indent <= parent_indent {
+                break;
+            }
+            spans.push(Span {
+                line: i + 1,
+                start: indent,
+                text: line[indent..].trim_end().to_string(),
+            });
+        }
+        i += 1;
+    }
+    (spans, i)
+}
+
+/// Strips matching quotes: (text, offset of text within value).
+fn unquote(value: &str) -> (&str, usize) {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return (inner, 1);
+        }
+    }
+    (value, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_write_files_and_runcmd() {
+        let content = r#"#cloud-config
+packages:
+  - nginx
+write_files:
+  - path: /etc/app/env
+    permissions: '0600'
+    content: |
+      DB_HOST=db.internal
+      DB_PASSWORD=hunter2
+
+  - encoding: b64
+    content: "QUJD"
+    path: /root/.token
+runcmd:
+- [ systemctl, restart, nginx ]
+- 'echo done'
+- |
+  curl -fsSL https://example.invalid/install.sh
+  sh install.sh
+final_message: up
+"#;
+        assert!(is_cloud_init("infra/vm.yaml", content));
+        assert!(is_cloud_init("deploy/user-data", ""));
+        assert!(!is_cloud_init("values.yaml", "image: nginx\n"));
+
+        let parsed = parse(content);
+        assert_eq!(parsed.write_files.len(), 2);
+        let env = &parsed.write_files[0];
+        assert_eq!(env.path.as_deref(), Some("/etc/app/env"));
+        assert!(!env.is_base64());
+        assert_eq!(
+            env.content[1],
+            Span {
+                line: 9,
+                start: 6,
+                text: "DB_PASSWORD=hunter2".to_string()
+            }
+        );
+        assert_eq!(env.content.len(), 2);
+
+        // Fields come in any order; the span points inside the quotes
+        let token = &parsed.write_files[1];
+        assert_eq!(token.path.as_deref(), Some("/root/.token"));
+        assert!(token.is_base64());
+        assert_eq!(token.content[0].line, 12);
+        assert_eq!(token.content[0].start, 14);
+        assert_eq!(token.content[0].text, "QUJD");
+
+        let commands: Vec<(usize, &str)> = parsed
+            .runcmd
+            .iter()
+            .map(|s| (s.line, s.text.as_str()))
+            .collect();
+        assert_eq!(
+            commands,
+            vec![
+                (15, "[ systemctl, restart, nginx ]"),
+                (16, "echo done"),
+                (18, "curl -fsSL https://example.invalid/install.sh"),
+                (19, "sh install.sh"),
+            ]
+        );
+    }
+}
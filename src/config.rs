@@ -0,0 +1,316 @@
+use crate::allowlist::Allowlist;
+use crate::rules::{CustomRules, RULE_BUDGET};
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const CONFIG_FILE: &str = "sieve.toml";
+pub const CONFIG_ENV: &str = "SIEVE_CONFIG";
+
+// Severity bands: High >= 80, Medium >= 60, Low >= 40; anything lower is noise
+pub const HIGH_SCORE: u8 = 80;
+pub const MEDIUM_SCORE: u8 = 60;
+pub const LOW_SCORE: u8 = 40;
+
+#[derive(Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct Config {
+    pub scan: ScanConfig,
+    pub risk: RiskConfig,
+    pub gate: GateConfig,
+    pub syslog: SyslogConfig,
+}
+
+/// How findings map onto syslog messages (`--syslog`).
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SyslogConfig {
+    /// Facility name: `user`, `auth`, `authpriv`, `daemon`, or `local0`-`local7`
+    pub facility: String,
+    /// Syslog severity names (`crit`, `err`, `warning`, `notice`, ...) per finding severity
+    pub high: String,
+    pub medium: String,
+    pub low: String,
+}
+
+impl Default for SyslogConfig {
+    fn default() -> Self {
+        SyslogConfig {
+            facility: "auth".to_string(),
+            high: "err".to_string(),
+            medium: "warning".to_string(),
+            low: "notice".to_string(),
+        }
+    }
+}
+
+/// Exit-code policy on top of the severity gate.
+#[derive(Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct GateConfig {
+    /// Rule ids that fail the run whenever they match, regardless of score or
+    /// `--gate` (`--always-fail-rule` adds to these)
+    pub always_fail_rules: Vec<String>,
+}
+
+/// Knobs that influence how individual lines are scored.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ScanConfig {
+    /// Extra public/test credentials to downgrade, on top of the built-in list
+    pub public_values: Vec<String>,
+    /// Strip whitespace from multi-line secrets (PEM blocks) before fingerprinting,
+    /// so re-wrapping a key doesn't invalidate its baseline entry
+    pub normalize_fingerprints: bool,
+    /// Cap on redacted preview length in characters (`--max-preview-length`)
+    pub max_preview_length: Option<usize>,
+    /// Extra globs for test/fixture paths that get the test-file penalty
+    pub test_paths: Vec<String>,
+    #[serde(skip)]
+    pub test_globs: Option<GlobSet>,
+    /// Values at least this long get the entropy checks
+    pub entropy_min_length: usize,
+    /// Shannon entropy (bits/char) above which a value scores as random
+    pub high_entropy: f32,
+    /// Lower entropy cutoff, applied only to values of `moderate_entropy_min_length`+
+    pub moderate_entropy: f32,
+    pub moderate_entropy_min_length: usize,
+    /// Values shorter than this are penalized as unlikely secrets
+    pub min_value_length: usize,
+    /// Findings scoring below this are dropped (`--min-score`); defaults to
+    /// 60, or 40 when Low findings are included
+    pub min_score: Option<u8>,
+    /// Collect Low findings (score 40-59) too (`--include-low`)
+    pub include_low: bool,
+    /// Also scan the decoded text of base64 values (`--decode-base64`)
+    pub decode_base64: bool,
+    /// Fold findings of any rule on consecutive lines into one ranged
+    /// finding (`--merge-adjacent`); block rules are always folded
+    pub merge_adjacent: bool,
+    /// Collapse repeats of one secret within a file into a single finding
+    /// listing their lines (`--dedupe`)
+    pub dedupe: bool,
+    /// Lines longer than this (minified code) only get the fixed-format
+    /// token rules, not the entropy and assignment heuristics
+    pub max_line_length: usize,
+    /// Files larger than this many bytes are skipped unread
+    /// (`--max-file-size`); 0 means no limit
+    pub max_file_size: u64,
+    /// When non-empty, only these rules run (`--enable-rule`)
+    pub enable_rules: Vec<String>,
+    /// Rules that never run, even if enabled (`--disable-rule`)
+    pub disable_rules: Vec<String>,
+    /// Values dropped wherever they appear (`allowlist.txt`, `--allowlist`)
+    #[serde(skip)]
+    pub allowlist: Allowlist,
+    /// Organization-specific rules (`sieve-rules.toml`, `--rules`)
+    #[serde(skip)]
+    pub custom_rules: CustomRules,
+    /// Stop scanning a file after this long (`--timeout-per-file`)
+    #[serde(skip)]
+    pub timeout_per_file: Option<Duration>,
+    /// Threads scanning files in parallel (`--concurrency`); None means one
+    /// per logical CPU
+    #[serde(skip)]
+    pub concurrency: Option<NonZeroUsize>,
+    /// Time one custom rule may take on one line before the line is skipped
+    #[serde(skip)]
+    pub rule_budget: Duration,
+    /// Rules collected whatever their score, so `always_fail_rules` can gate
+    /// on them (copied from the gate config)
+    #[serde(skip)]
+    pub always_report: Vec<String>,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        ScanConfig {
+            public_values: Vec::new(),
+            normalize_fingerprints: true,
+            max_preview_length: None,
+            test_paths: Vec::new(),
+            test_globs: None,
+            entropy_min_length: 16,
+            high_entropy: 4.0,
+            moderate_entropy: 3.0,
+            moderate_entropy_min_length: 20,
+            min_value_length: 8,
+            min_score: None,
+            include_low: false,
+            decode_base64: false,
+            merge_adjacent: false,
+            dedupe: false,
+            max_line_length: 1000,
+            max_file_size: 5 * 1024 * 1024,
+            enable_rules: Vec::new(),
+            disable_rules: Vec::new(),
+            allowlist: Allowlist::default(),
+            custom_rules: CustomRules::default(),
+            timeout_per_file: None,
+            concurrency: None,
+            rule_budget: RULE_BUDGET,
+            always_report: Vec::new(),
+        }
+    }
+}
+
+impl ScanConfig {
+    /// Whether `rule_id` may report: listed in `enable_rules` (or that list
+    /// is empty) and not in `disable_rules`.
+    pub fn rule_enabled(&self, rule_id: &str) -> bool {
+        (self.enable_rules.is_empty() || self.enable_rules.iter().any(|r| r == rule_id))
+            && !self.disable_rules.iter().any(|r| r == rule_id)
+    }
+
+    pub fn effective_min_score(&self) -> u8 {
+        self.min_score.unwrap_or(if self.include_low {
+            LOW_SCORE
+        } else {
+            MEDIUM_SCORE
+        })
+    }
+}
+
+/// Weights for the whole-scan risk score (see `risk::compute_scan_risk`).
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct RiskConfig {
+    pub high_weight: f64,
+    pub medium_weight: f64,
+    pub low_weight: f64,
+    /// Per-rule multipliers, e.g. `PRIVATE_KEY_BLOCK = 2.0`; unlisted rules use 1.0
+    pub rule_weights: HashMap<String, f64>,
+    /// Weighted total at which the score reaches ~63/100; lower is stricter
+    pub saturation: f64,
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        RiskConfig {
+            high_weight: 25.0,
+            medium_weight: 10.0,
+            low_weight: 3.0,
+            rule_weights: HashMap::from([
+                ("PRIVATE_KEY_BLOCK".to_string(), 1.5),
+                ("ENCODED_PRIVATE_KEY".to_string(), 1.5),
+                ("PRIVATE_KEY_IN_CERT".to_string(), 1.5),
+                ("AWS_ACCESS_KEY".to_string(), 1.5),
+                ("AWS_SESSION_TOKEN".to_string(), 1.5),
+            ]),
+            saturation: 30.0,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config from `--config`, then `$SIEVE_CONFIG`, then `sieve.toml`
+    /// in the current directory, falling back to defaults when the default file
+    /// does not exist. An explicitly named file must exist.
+    pub fn load(flag: Option<&Path>) -> Result<Self> {
+        let path = resolve_path(flag, std::env::var(CONFIG_ENV).ok(), CONFIG_FILE);
+        if path != Path::new(CONFIG_FILE) && !path.exists() {
+            anyhow::bail!("Config file not found: {}", path.display());
+        }
+        Self::load_from(&path)
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut config: Config = toml::from_str(&content)
+            .with_context(|| format!("Invalid config in {}", path.display()))?;
+        config.scan.test_globs = compile_globs(&config.scan.test_paths)
+            .with_context(|| format!("Invalid test_paths in {}", path.display()))?;
+        Ok(config)
+    }
+}
+
+/// Compiles glob patterns once up front; `None` when there are none.
+pub fn compile_globs(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).with_context(|| format!("Invalid glob '{}'", pattern))?);
+    }
+    Ok(Some(builder.build()?))
+}
+
+/// Picks a settings file location. Precedence: CLI flag > environment variable
+/// > default relative to the current directory.
+pub fn resolve_path(flag: Option<&Path>, env_value: Option<String>, default: &str) -> PathBuf {
+    flag.map(Path::to_path_buf)
+        .or_else(|| env_value.filter(|v| !v.is_empty()).map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(default))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_public_values() {
+        let config: Config = toml::from_str(
+            r#"
+[scan]
+public_values = ["my_docs_key_1234567890"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.scan.public_values, vec!["my_docs_key_1234567890"]);
+    }
+
+    #[test]
+    fn test_missing_file_uses_defaults() {
+        let config = Config::load_from(Path::new("does/not/exist/sieve.toml")).unwrap();
+        assert!(config.scan.public_values.is_empty());
+        assert!(config.scan.normalize_fingerprints);
+    }
+
+    #[test]
+    fn test_resolve_path_precedence() {
+        let flag = Path::new("flag.toml");
+        let env = Some("env.toml".to_string());
+        assert_eq!(resolve_path(Some(flag), env.clone(), CONFIG_FILE), flag);
+        assert_eq!(
+            resolve_path(None, env, CONFIG_FILE),
+            PathBuf::from("env.toml")
+        );
+        assert_eq!(
+            resolve_path(None, Some(String::new()), CONFIG_FILE),
+            PathBuf::from(CONFIG_FILE)
+        );
+    }
+
+    #[test]
+    fn test_env_config_loaded_over_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("org-policy.toml");
+        fs::write(&shared, "[scan]\npublic_values = [\"ORG_SHARED_SAMPLE\"]\n").unwrap();
+
+        let path = resolve_path(
+            None,
+            Some(shared.to_string_lossy().to_string()),
+            CONFIG_FILE,
+        );
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.scan.public_values, vec!["ORG_SHARED_SAMPLE"]);
+    }
+
+    #[test]
+    fn test_scan_thresholds_default_when_unset() {
+        let config: Config = toml::from_str("[scan]\nhigh_entropy = 4.5\n").unwrap();
+        assert_eq!(config.scan.high_entropy, 4.5);
+        assert_eq!(config.scan.moderate_entropy, 3.0);
+        assert_eq!(config.scan.effective_min_score(), 60);
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Marker comments delimiting the Sieve-managed block, so uninstall can leave
+// the rest of a hand-written hook alone
+const BEGIN_MARKER: &str = "# >>> sieve pre-commit >>>";
+const END_MARKER: &str = "# <<< sieve pre-commit <<<";
+const HOOK_COMMAND: &str = "sieve scan --staged --no-tui --strict || exit 1";
+
+#[derive(Debug, PartialEq)]
+pub enum InstallOutcome {
+    Created,
+    /// Inserted into an existing hook (`--force`)
+    Merged,
+    AlreadyInstalled,
+}
+
+/// The hooks directory for the current repository, honouring `core.hooksPath`.
+pub fn hooks_dir() -> Result<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks"])
+        .output()
+        .context("Failed to run git rev-parse")?;
+    if !output.status.success() {
+        anyhow::bail!("Not a git repository");
+    }
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    ))
+}
+
+fn block() -> String {
+    format!("{}\n{}\n{}\n", BEGIN_MARKER, HOOK_COMMAND, END_MARKER)
+}
+
+/// Writes the pre-commit hook. An existing hook that Sieve didn't create is
+/// left alone unless `force` is set, in which case the Sieve block is inserted
+/// right after its shebang so it runs before the rest of the script.
+pub fn install(hooks_dir: &Path, force: bool) -> Result<InstallOutcome> {
+    let path = hooks_dir.join("pre-commit");
+    let (content, outcome) = match fs::read_to_string(&path) {
+        Ok(existing) if existing.contains(BEGIN_MARKER) => {
+            return Ok(InstallOutcome::AlreadyInstalled);
+        }
+        Ok(_) if !force => {
+            anyhow::bail!(
+                "{} already exists; re-run with --force to add Sieve to it",
+                path.display()
+            );
+        }
+        Ok(existing) => {
+            let (shebang, rest) = match existing.split_once('\n') {
+                Some((first, rest)) if first.starts_with("#!") => (first, rest),
+                _ => ("#!/bin/sh", existing.as_str()),
+            };
+            (
+                format!("{}\n{}{}", shebang, block(), rest),
+                InstallOutcome::Merged,
+            )
+        }
+        Err(_) => (format!("#!/bin/sh\n{}", block()), InstallOutcome::Created),
+    };
+
+    fs::create_dir_all(hooks_dir)
+        .with_context(|| format!("Failed to create {}", hooks_dir.display()))?;
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(outcome)
+}
+
+/// Removes the Sieve block from the pre-commit hook, deleting the file if
+/// nothing but a shebang is left. Returns false if no block was found.
+pub fn uninstall(hooks_dir: &Path) -> Result<bool> {
+    let path = hooks_dir.join("pre-commit");
+    let Ok(existing) = fs::read_to_string(&path) else {
+        return Ok(false);
+    };
+    let (Some(start), Some(end)) = (existing.find(BEGIN_MARKER), existing.find(END_MARKER)) else {
+        return Ok(false);
+    };
+
+    let after = existing[end + END_MARKER.len()..]
+        .strip_prefix('\n')
+        .unwrap_or(&existing[end + END_MARKER.len()..]);
+    let remaining = format!("{}{}", &existing[..start], after);
+
+    if remaining
+        .lines()
+        .all(|l| l.trim().is_empty() || l.starts_with("#!"))
+    {
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    } else {
+        fs::write(&path, remaining)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_and_uninstall_fresh_hook() {
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = dir.path().join(".git/hooks");
+
+        assert_eq!(install(&hooks, false).unwrap(), InstallOutcome::Created);
+        let script = fs::read_to_string(hooks.join("pre-commit")).unwrap();
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("sieve scan --staged --no-tui --strict"));
+        assert_eq!(
+            install(&hooks, false).unwrap(),
+            InstallOutcome::AlreadyInstalled
+        );
+
+        assert!(uninstall(&hooks).unwrap());
+        assert!(!hooks.join("pre-commit").exists());
+        assert!(!uninstall(&hooks).unwrap());
+    }
+
+    #[test]
+    fn test_existing_hook_requires_force_and_survives_uninstall() {
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = dir.path().join(".git/hooks");
+        fs::create_dir_all(&hooks).unwrap();
+        let original = "#!/bin/bash\ncargo fmt --check\n";
+        fs::write(hooks.join("pre-commit"), original).unwrap();
+
+        assert!(install(&hooks, false).is_err());
+        assert_eq!(
+            fs::read_to_string(hooks.join("pre-commit")).unwrap(),
+            original
+        );
+
+        assert_eq!(install(&hooks, true).unwrap(), InstallOutcome::Merged);
+        let merged = fs::read_to_string(hooks.join("pre-commit")).unwrap();
+        assert!(merged.starts_with("#!/bin/bash\n# >>> sieve"));
+        assert!(merged.ends_with("cargo fmt --check\n"));
+
+        assert!(uninstall(&hooks).unwrap());
+        assert_eq!(
+            fs::read_to_string(hooks.join("pre-commit")).unwrap(),
+            original
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use env_logger::{Builder, Logger};
+use log::LevelFilter;
+use std::io::Write;
+
+/// Environment variable read when `--log-level` isn't given.
+pub const LOG_ENV: &str = "SIEVE_LOG";
+const TARGET: &str = "sieve";
+
+/// Sends diagnostics to stderr, keeping stdout for report output. The level
+/// comes from `--log-level`, then `$SIEVE_LOG` (full `env_logger` syntax, e.g.
+/// `sieve=debug,ignore=trace`); otherwise warnings and errors are shown, plus
+/// info with `--verbose`, or only errors with `--quiet`.
+pub fn init(level: Option<LevelFilter>, verbose: bool, quiet: bool) {
+    let logger = build(
+        level,
+        std::env::var(LOG_ENV).ok().as_deref(),
+        verbose,
+        quiet,
+    );
+    let max = logger.filter();
+    if log::set_boxed_logger(Box::new(logger)).is_ok() {
+        log::set_max_level(max);
+    }
+}
+
+fn build(level: Option<LevelFilter>, env: Option<&str>, verbose: bool, quiet: bool) -> Logger {
+    let mut builder = Builder::new();
+    builder.format(|buf, record| {
+        let label = match record.level() {
+            log::Level::Error => "Error",
+            log::Level::Warn => "Warning",
+            log::Level::Info => "Info",
+            log::Level::Debug => "Debug",
+            log::Level::Trace => "Trace",
+        };
+        writeln!(buf, "{}: {}", label, record.args())
+    });
+    let sieve_level = match (level, env) {
+        (Some(level), _) => level,
+        (None, Some(env)) => {
+            builder.parse_filters(env);
+            return builder.build();
+        }
+        (None, None) if quiet => LevelFilter::Error,
+        (None, None) if verbose => LevelFilter::Info,
+        (None, None) => LevelFilter::Warn,
+    };
+    // Dependencies (e.g. the directory walker) only get a say at warn and
+    // above, so `--log-level debug` is about Sieve's own rule decisions
+    builder
+        .filter_level(sieve_level.min(LevelFilter::Warn))
+        .filter_module(TARGET, sieve_level);
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::{Level, Log, Metadata};
+
+    fn logs(logger: &Logger, level: Level) -> bool {
+        logger.enabled(&Metadata::builder().level(level).target("sieve").build())
+    }
+
+    #[test]
+    fn test_debug_logs_only_at_debug_level() {
+        let default = build(None, None, false, false);
+        assert!(logs(&default, Level::Warn));
+        assert!(!logs(&default, Level::Info));
+        assert!(!logs(&default, Level::Debug));
+
+        assert!(logs(&build(None, None, true, false), Level::Info));
+        assert!(!logs(&build(None, None, true, false), Level::Debug));
+        // --quiet keeps only errors, e.g. the TUI fallback warning goes
+        assert!(!logs(&build(None, None, false, true), Level::Warn));
+        assert!(logs(&build(None, None, false, true), Level::Error));
+
+        let debug = build(Some(LevelFilter::Debug), None, false, false);
+        assert!(logs(&debug, Level::Debug));
+        assert!(!logs(&debug, Level::Trace));
+
+        // SIEVE_LOG applies unless --log-level overrides it
+        assert!(logs(
+            &build(None, Some("debug"), false, false),
+            Level::Debug
+        ));
+        assert!(!logs(
+            &build(Some(LevelFilter::Error), Some("debug"), false, false),
+            Level::Warn
+        ));
+    }
+}